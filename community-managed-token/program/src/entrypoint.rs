@@ -0,0 +1,7 @@
+//! Program entrypoint.
+
+use solana_program::entrypoint;
+
+use crate::processor::process_instruction;
+
+entrypoint!(process_instruction);
@@ -0,0 +1,52 @@
+//! Error type for the community-managed-token program.
+
+use solana_program::{decode_error::DecodeError, program_error::ProgramError};
+use thiserror::Error;
+
+/// Errors that may be returned by the community-managed-token program.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum CommunityManagedTokenError {
+    /// The instruction immediately preceding this one is not a
+    /// secp256k1 program instruction with the expected shape.
+    #[error("missing or malformed secp256k1 instruction")]
+    MissingSecp256k1Instruction,
+
+    /// The secp256k1 signer recovered from the preceding instruction
+    /// does not match the mint's configured authorizer.
+    #[error("secp256k1 signer does not match the mint's authorizer")]
+    AuthorizerMismatch,
+
+    /// The authorization's `expiry_slot` has already passed.
+    #[error("authorization has expired")]
+    AuthorizationExpired,
+
+    /// The authorization's nonce has already been consumed.
+    #[error("nonce has already been used")]
+    NonceAlreadyUsed,
+
+    /// An account owner does not have a live allowlist entry for the
+    /// mint.
+    #[error("owner is not on the mint's allowlist")]
+    NotAllowlisted,
+
+    /// A metadata field exceeded its maximum length.
+    #[error("metadata field exceeds its maximum length")]
+    MetadataTooLong,
+
+    /// A batch transfer was submitted with no legs, or with more
+    /// accounts than its amount list calls for.
+    #[error("batch transfer has no legs or has surplus accounts")]
+    InvalidBatch,
+}
+
+impl From<CommunityManagedTokenError> for ProgramError {
+    fn from(e: CommunityManagedTokenError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for CommunityManagedTokenError {
+    fn type_of() -> &'static str {
+        "CommunityManagedTokenError"
+    }
+}
@@ -0,0 +1,925 @@
+//! Instructions supported by the community-managed-token program.
+//!
+//! Every instruction that moves, mints, or burns tokens requires the
+//! mint's community authority (see [`crate::state::MintConfig`]) as a
+//! co-signer alongside the usual `spl_token` owner/delegate, except
+//! [`CommunityManagedTokenInstruction::TransferWithAuthorization`] and
+//! [`CommunityManagedTokenInstruction::AllowlistedTransfer`], which each
+//! replace that co-signature with a cheaper one-time check: an off-chain
+//! secp256k1 approval, or standing membership in the mint's allowlist.
+
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent, system_program, sysvar,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::state::{AllowlistEntry, Metadata, MintConfig};
+
+/// Instructions supported by the community-managed-token program.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommunityManagedTokenInstruction {
+    /// Create and initialize a new community-managed mint.
+    ///
+    /// The mint's `MintConfig` PDA is created alongside it and becomes
+    /// the underlying `spl_token` mint and freeze authority.
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` New mint to create.
+    /// 1. `[writable]` New `MintConfig` PDA for this mint.
+    /// 2. `[signer]` Community authority to record in the config.
+    /// 3. `[writable, signer]` Payer.
+    /// 4. `[]` System program.
+    /// 5. `[]` SPL Token program.
+    /// 6. `[]` Rent sysvar.
+    InitializeMint {
+        /// Number of base 10 digits to the right of the decimal place.
+        decimals: u8,
+    },
+
+    /// Create a new, frozen token account for an owner.
+    ///
+    /// Accounts:
+    /// 0. `[]` Mint.
+    /// 1. `[]` `MintConfig` PDA for the mint.
+    /// 2. `[signer]` Community authority.
+    /// 3. `[writable, signer]` Payer.
+    /// 4. `[writable]` New token account (the owner's associated token
+    ///    account).
+    /// 5. `[]` Owner.
+    /// 6. `[]` System program.
+    /// 7. `[]` SPL Token program.
+    /// 8. `[]` SPL Associated Token Account program.
+    /// 9. `[]` Rent sysvar.
+    InitializeAccount,
+
+    /// Mint new tokens into a frozen account, leaving it frozen.
+    ///
+    /// Accounts:
+    /// 0. `[writable]` Mint.
+    /// 1. `[]` `MintConfig` PDA for the mint.
+    /// 2. `[signer]` Community authority.
+    /// 3. `[writable]` Destination token account.
+    MintTo {
+        /// Amount to mint, in the mint's smallest unit.
+        amount: u64,
+    },
+
+    /// Thaw, transfer, and refreeze `amount` tokens between two
+    /// community-managed accounts.
+    ///
+    /// Accounts:
+    /// 0. `[writable]` Source token account.
+    /// 1. `[writable]` Destination token account.
+    /// 2. `[]` Mint.
+    /// 3. `[]` `MintConfig` PDA for the mint.
+    /// 4. `[signer]` Source account owner.
+    /// 5. `[signer]` Community authority.
+    Transfer {
+        /// Amount to transfer, in the mint's smallest unit.
+        amount: u64,
+    },
+
+    /// Same as [`Self::Transfer`], but moves tokens via an approved
+    /// delegate instead of the account owner.
+    ///
+    /// Accounts:
+    /// 0. `[writable]` Source token account.
+    /// 1. `[writable]` Destination token account.
+    /// 2. `[]` Mint.
+    /// 3. `[]` `MintConfig` PDA for the mint.
+    /// 4. `[signer]` Delegate.
+    /// 5. `[signer]` Community authority.
+    TransferWithDelegate {
+        /// Amount to transfer, in the mint's smallest unit.
+        amount: u64,
+    },
+
+    /// Approve a delegate to move up to `amount` tokens out of an
+    /// account, without lifting the freeze afterwards.
+    ///
+    /// Accounts:
+    /// 0. `[writable]` Source token account.
+    /// 1. `[]` Mint.
+    /// 2. `[]` `MintConfig` PDA for the mint.
+    /// 3. `[signer]` Source account owner.
+    /// 4. `[signer]` Community authority.
+    /// 5. `[]` Delegate.
+    Approve {
+        /// Amount the delegate may transfer, in the mint's smallest
+        /// unit.
+        amount: u64,
+    },
+
+    /// Thaw, burn, and refreeze `amount` tokens.
+    ///
+    /// Accounts:
+    /// 0. `[writable]` Token account.
+    /// 1. `[writable]` Mint.
+    /// 2. `[]` `MintConfig` PDA for the mint.
+    /// 3. `[signer]` Account owner.
+    /// 4. `[signer]` Community authority.
+    Burn {
+        /// Amount to burn, in the mint's smallest unit.
+        amount: u64,
+    },
+
+    /// Seize the mint and freeze authority of an existing `spl_token`
+    /// mint, creating its `MintConfig` and handing control to a new
+    /// community authority.
+    ///
+    /// Accounts:
+    /// 0. `[writable]` Mint.
+    /// 1. `[writable]` New `MintConfig` PDA for the mint.
+    /// 2. `[writable, signer]` Current mint authority (also pays for the
+    ///    new config account).
+    /// 3. `[signer]` Current freeze authority.
+    /// 4. `[]` New community authority.
+    /// 5. `[]` System program.
+    /// 6. `[]` SPL Token program.
+    Wrap,
+
+    /// Set or rotate the secp256k1 authorizer for a mint.
+    ///
+    /// Accounts:
+    /// 0. `[writable]` `MintConfig` PDA for the mint.
+    /// 1. `[signer]` Community authority.
+    SetAuthorizer {
+        /// The 20-byte Ethereum-style address recovered from the
+        /// authorizer's secp256k1 signatures.
+        authorizer: [u8; 20],
+    },
+
+    /// Thaw, transfer, and refreeze `amount` tokens, authorized by an
+    /// off-chain secp256k1 signature instead of the community
+    /// authority's direct co-signature.
+    ///
+    /// The preceding instruction in the transaction must be a
+    /// `secp256k1_program` instruction recovering the mint's configured
+    /// `authorizer` over the message
+    /// `(source_owner, dest_owner, mint, amount, expiry_slot, nonce)`.
+    ///
+    /// Accounts:
+    /// 0. `[writable]` Source token account.
+    /// 1. `[writable]` Destination token account.
+    /// 2. `[]` Mint.
+    /// 3. `[]` `MintConfig` PDA for the mint.
+    /// 4. `[writable]` Nonce marker PDA for `(mint, nonce)`, created by
+    ///    this instruction to prevent replay.
+    /// 5. `[writable, signer]` Source account owner, also pays for the
+    ///    nonce marker.
+    /// 6. `[]` System program.
+    /// 7. `[]` SPL Token program.
+    /// 8. `[]` Instructions sysvar.
+    TransferWithAuthorization {
+        /// Amount to transfer, in the mint's smallest unit.
+        amount: u64,
+        /// Last slot at which this authorization is valid.
+        expiry_slot: u64,
+        /// Unique value binding this authorization to one
+        /// replay-protection PDA.
+        nonce: u64,
+    },
+
+    /// Add `owner` to a mint's allowlist, authority-signed.
+    ///
+    /// Accounts:
+    /// 0. `[]` Mint.
+    /// 1. `[]` `MintConfig` PDA for the mint.
+    /// 2. `[signer]` Community authority.
+    /// 3. `[writable]` New allowlist entry PDA for `(mint, owner)`.
+    /// 4. `[]` Owner to allowlist.
+    /// 5. `[writable, signer]` Payer.
+    /// 6. `[]` System program.
+    AddToAllowlist,
+
+    /// Remove `owner` from a mint's allowlist, authority-signed.
+    ///
+    /// Accounts:
+    /// 0. `[]` Mint.
+    /// 1. `[]` `MintConfig` PDA for the mint.
+    /// 2. `[signer]` Community authority.
+    /// 3. `[writable]` Allowlist entry PDA for `(mint, owner)` to close.
+    /// 4. `[]` Owner to remove.
+    /// 5. `[writable]` Account to receive the reclaimed rent.
+    RemoveFromAllowlist,
+
+    /// Thaw, transfer, and refreeze `amount` tokens between two
+    /// accounts whose owners are both allowlisted for the mint, without
+    /// the community authority as a signer.
+    ///
+    /// Accounts:
+    /// 0. `[writable]` Source token account.
+    /// 1. `[writable]` Destination token account.
+    /// 2. `[]` Mint.
+    /// 3. `[]` `MintConfig` PDA for the mint.
+    /// 4. `[]` Source owner's allowlist entry PDA.
+    /// 5. `[]` Destination owner's allowlist entry PDA.
+    /// 6. `[signer]` Source account owner.
+    /// 7. `[]` Destination account owner.
+    AllowlistedTransfer {
+        /// Amount to transfer, in the mint's smallest unit.
+        amount: u64,
+    },
+
+    /// Initialize a community-managed NFT: a `decimals = 0` mint with
+    /// exactly one token minted to `destination`, after which minting is
+    /// permanently disabled.
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` New mint to create.
+    /// 1. `[writable]` New `MintConfig` PDA for this mint.
+    /// 2. `[signer]` Community authority to record in the config.
+    /// 3. `[writable, signer]` Payer.
+    /// 4. `[writable]` Destination token account (the owner's
+    ///    associated token account).
+    /// 5. `[]` Destination owner.
+    /// 6. `[]` System program.
+    /// 7. `[]` SPL Token program.
+    /// 8. `[]` SPL Associated Token Account program.
+    /// 9. `[]` Rent sysvar.
+    InitializeNft,
+
+    /// Write name/symbol/uri metadata for a mint, creating its
+    /// `Metadata` PDA on first use.
+    ///
+    /// Accounts:
+    /// 0. `[]` Mint.
+    /// 1. `[]` `MintConfig` PDA for the mint.
+    /// 2. `[signer]` Community authority.
+    /// 3. `[writable]` `Metadata` PDA for the mint.
+    /// 4. `[writable, signer]` Payer.
+    /// 5. `[]` System program.
+    SetMetadata {
+        /// Display name of the NFT.
+        name: String,
+        /// Ticker-style symbol of the NFT.
+        symbol: String,
+        /// URI pointing at the NFT's off-chain (or data://) content.
+        uri: String,
+    },
+
+    /// Thaw, transfer, and refreeze many (source, destination) pairs in
+    /// one atomic instruction, so a failure in any leg reverts every
+    /// other leg too.
+    ///
+    /// Accounts:
+    /// 0. `[]` Mint.
+    /// 1. `[]` `MintConfig` PDA for the mint.
+    /// 2. `[signer]` Community authority.
+    /// 3.. One account-meta group per entry in `amounts`, in order:
+    ///    - `[signer]` Source account owner.
+    ///    - `[writable]` Source token account.
+    ///    - `[writable]` Destination token account.
+    BatchTransfer {
+        /// Amount to transfer for each leg, in the same order as the
+        /// trailing account-meta groups.
+        amounts: Vec<u64>,
+    },
+
+    /// Reverse of [`Self::Wrap`]: hand the mint and freeze authority back
+    /// to caller-specified keys and close the mint's `MintConfig`,
+    /// turning it back into a standard `spl_token` mint.
+    ///
+    /// This does not thaw any token accounts that were frozen while the
+    /// mint was community-managed; any still-frozen account stays frozen
+    /// until `new_freeze_authority` thaws it directly with a plain
+    /// `spl_token::instruction::thaw_account`.
+    ///
+    /// Accounts:
+    /// 0. `[writable]` Mint.
+    /// 1. `[writable]` `MintConfig` PDA for the mint, closed by this
+    ///    instruction.
+    /// 2. `[writable, signer]` Community authority (receives the closed
+    ///    config's rent).
+    /// 3. `[]` New mint authority.
+    /// 4. `[]` New freeze authority.
+    /// 5. `[]` SPL Token program.
+    Unwrap,
+}
+
+impl CommunityManagedTokenInstruction {
+    /// Packs a [`CommunityManagedTokenInstruction`] into a byte buffer.
+    pub fn pack(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(32);
+        match self {
+            Self::InitializeMint { decimals } => {
+                buf.push(0);
+                buf.push(*decimals);
+            }
+            Self::InitializeAccount => buf.push(1),
+            Self::MintTo { amount } => {
+                buf.push(2);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::Transfer { amount } => {
+                buf.push(3);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::TransferWithDelegate { amount } => {
+                buf.push(4);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::Approve { amount } => {
+                buf.push(5);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::Burn { amount } => {
+                buf.push(6);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::Wrap => buf.push(7),
+            Self::SetAuthorizer { authorizer } => {
+                buf.push(8);
+                buf.extend_from_slice(authorizer);
+            }
+            Self::TransferWithAuthorization {
+                amount,
+                expiry_slot,
+                nonce,
+            } => {
+                buf.push(9);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&expiry_slot.to_le_bytes());
+                buf.extend_from_slice(&nonce.to_le_bytes());
+            }
+            Self::AddToAllowlist => buf.push(10),
+            Self::RemoveFromAllowlist => buf.push(11),
+            Self::AllowlistedTransfer { amount } => {
+                buf.push(12);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::InitializeNft => buf.push(13),
+            Self::SetMetadata { name, symbol, uri } => {
+                buf.push(14);
+                pack_string(&mut buf, name);
+                pack_string(&mut buf, symbol);
+                pack_string(&mut buf, uri);
+            }
+            Self::BatchTransfer { amounts } => {
+                buf.push(15);
+                buf.extend_from_slice(&(amounts.len() as u32).to_le_bytes());
+                for amount in amounts {
+                    buf.extend_from_slice(&amount.to_le_bytes());
+                }
+            }
+            Self::Unwrap => buf.push(16),
+        }
+        buf
+    }
+
+    /// Unpacks a [`CommunityManagedTokenInstruction`] from a byte
+    /// buffer.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(match tag {
+            0 => Self::InitializeMint {
+                decimals: *rest.first().ok_or(ProgramError::InvalidInstructionData)?,
+            },
+            1 => Self::InitializeAccount,
+            2 => Self::MintTo {
+                amount: unpack_u64(rest)?,
+            },
+            3 => Self::Transfer {
+                amount: unpack_u64(rest)?,
+            },
+            4 => Self::TransferWithDelegate {
+                amount: unpack_u64(rest)?,
+            },
+            5 => Self::Approve {
+                amount: unpack_u64(rest)?,
+            },
+            6 => Self::Burn {
+                amount: unpack_u64(rest)?,
+            },
+            7 => Self::Wrap,
+            8 => {
+                let authorizer: [u8; 20] = rest
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetAuthorizer { authorizer }
+            }
+            9 => {
+                if rest.len() != 24 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                Self::TransferWithAuthorization {
+                    amount: unpack_u64(&rest[0..8])?,
+                    expiry_slot: unpack_u64(&rest[8..16])?,
+                    nonce: unpack_u64(&rest[16..24])?,
+                }
+            }
+            10 => Self::AddToAllowlist,
+            11 => Self::RemoveFromAllowlist,
+            12 => Self::AllowlistedTransfer {
+                amount: unpack_u64(rest)?,
+            },
+            13 => Self::InitializeNft,
+            14 => {
+                let (name, rest) = unpack_string(rest)?;
+                let (symbol, rest) = unpack_string(rest)?;
+                let (uri, _rest) = unpack_string(rest)?;
+                Self::SetMetadata { name, symbol, uri }
+            }
+            15 => {
+                if rest.len() < 4 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let count = u32::from_le_bytes(rest[0..4].try_into().unwrap()) as usize;
+                let payload = rest.get(4..).ok_or(ProgramError::InvalidInstructionData)?;
+                if payload.len() != count.saturating_mul(8) {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let mut amounts = Vec::with_capacity(count);
+                let mut cursor = payload;
+                for _ in 0..count {
+                    amounts.push(unpack_u64(
+                        cursor.get(0..8).ok_or(ProgramError::InvalidInstructionData)?,
+                    )?);
+                    cursor = &cursor[8..];
+                }
+                Self::BatchTransfer { amounts }
+            }
+            16 => Self::Unwrap,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+}
+
+fn unpack_u64(bytes: &[u8]) -> Result<u64, ProgramError> {
+    let bytes: [u8; 8] = bytes
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Appends a `u32`-length-prefixed UTF-8 string to `buf`.
+fn pack_string(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+/// Reads a `u32`-length-prefixed UTF-8 string, returning it and the
+/// remaining bytes.
+fn unpack_string(bytes: &[u8]) -> Result<(String, &[u8]), ProgramError> {
+    if bytes.len() < 4 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let len = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+    let rest = &bytes[4..];
+    if rest.len() < len {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let s = String::from_utf8(rest[..len].to_vec())
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    Ok((s, &rest[len..]))
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::InitializeMint`]
+/// instruction.
+pub fn create_initialize_mint_instruction(
+    mint: &Pubkey,
+    community_authority: &Pubkey,
+    payer: &Pubkey,
+    decimals: u8,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(*mint, true),
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*community_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(rent::id(), false),
+        ],
+        data: CommunityManagedTokenInstruction::InitializeMint { decimals }.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::InitializeAccount`]
+/// instruction for `owner`'s associated token account.
+pub fn create_initialize_account_instruction(
+    mint: &Pubkey,
+    owner: &Pubkey,
+    community_authority: &Pubkey,
+    payer: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let account = get_associated_token_address(owner, mint);
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*community_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(account, false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(rent::id(), false),
+        ],
+        data: CommunityManagedTokenInstruction::InitializeAccount.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::MintTo`] instruction.
+pub fn create_mint_to_instruction(
+    mint: &Pubkey,
+    destination_owner: &Pubkey,
+    community_authority: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let destination = get_associated_token_address(destination_owner, mint);
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*community_authority, true),
+            AccountMeta::new(destination, false),
+        ],
+        data: CommunityManagedTokenInstruction::MintTo { amount }.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::Transfer`] instruction.
+pub fn create_transfer_instruction(
+    source_owner: &Pubkey,
+    destination_owner: &Pubkey,
+    mint: &Pubkey,
+    community_authority: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let source = get_associated_token_address(source_owner, mint);
+    let destination = get_associated_token_address(destination_owner, mint);
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(source, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*source_owner, true),
+            AccountMeta::new_readonly(*community_authority, true),
+        ],
+        data: CommunityManagedTokenInstruction::Transfer { amount }.pack(),
+    })
+}
+
+/// Creates a
+/// [`CommunityManagedTokenInstruction::TransferWithDelegate`]
+/// instruction.
+pub fn create_transfer_with_delegate_instruction(
+    source_owner: &Pubkey,
+    destination_owner: &Pubkey,
+    delegate: &Pubkey,
+    mint: &Pubkey,
+    community_authority: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let source = get_associated_token_address(source_owner, mint);
+    let destination = get_associated_token_address(destination_owner, mint);
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(source, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*delegate, true),
+            AccountMeta::new_readonly(*community_authority, true),
+        ],
+        data: CommunityManagedTokenInstruction::TransferWithDelegate { amount }.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::Approve`] instruction.
+pub fn create_approve_instruction(
+    mint: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    community_authority: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let source = get_associated_token_address(owner, mint);
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(*community_authority, true),
+            AccountMeta::new_readonly(*delegate, false),
+        ],
+        data: CommunityManagedTokenInstruction::Approve { amount }.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::Burn`] instruction.
+pub fn create_burn_instruction(
+    mint: &Pubkey,
+    owner: &Pubkey,
+    community_authority: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let account = get_associated_token_address(owner, mint);
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(account, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(*community_authority, true),
+        ],
+        data: CommunityManagedTokenInstruction::Burn { amount }.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::Wrap`] instruction.
+pub fn create_wrap_instruction(
+    mint: &Pubkey,
+    current_mint_authority: &Pubkey,
+    current_freeze_authority: &Pubkey,
+    new_community_authority: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(config, false),
+            AccountMeta::new(*current_mint_authority, true),
+            AccountMeta::new_readonly(*current_freeze_authority, true),
+            AccountMeta::new_readonly(*new_community_authority, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: CommunityManagedTokenInstruction::Wrap.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::SetAuthorizer`]
+/// instruction.
+pub fn create_set_authorizer_instruction(
+    mint: &Pubkey,
+    community_authority: &Pubkey,
+    authorizer: [u8; 20],
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*community_authority, true),
+        ],
+        data: CommunityManagedTokenInstruction::SetAuthorizer { authorizer }.pack(),
+    })
+}
+
+/// Creates a
+/// [`CommunityManagedTokenInstruction::TransferWithAuthorization`]
+/// instruction. The caller must place this immediately after a
+/// `secp256k1_program` instruction recovering the mint's authorizer over
+/// `(source_owner, dest_owner, mint, amount, expiry_slot, nonce)`.
+pub fn create_transfer_with_authorization_instruction(
+    source_owner: &Pubkey,
+    destination_owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    expiry_slot: u64,
+    nonce: u64,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let (nonce_marker, _) = MintConfig::find_nonce_address(mint, nonce, &crate::id());
+    let source = get_associated_token_address(source_owner, mint);
+    let destination = get_associated_token_address(destination_owner, mint);
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(source, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(nonce_marker, false),
+            AccountMeta::new(*source_owner, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+        data: CommunityManagedTokenInstruction::TransferWithAuthorization {
+            amount,
+            expiry_slot,
+            nonce,
+        }
+        .pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::AddToAllowlist`]
+/// instruction.
+pub fn create_add_to_allowlist_instruction(
+    mint: &Pubkey,
+    community_authority: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let (entry, _) = AllowlistEntry::find_address(mint, owner, &crate::id());
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*community_authority, true),
+            AccountMeta::new(entry, false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: CommunityManagedTokenInstruction::AddToAllowlist.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::RemoveFromAllowlist`]
+/// instruction.
+pub fn create_remove_from_allowlist_instruction(
+    mint: &Pubkey,
+    community_authority: &Pubkey,
+    owner: &Pubkey,
+    rent_recipient: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let (entry, _) = AllowlistEntry::find_address(mint, owner, &crate::id());
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*community_authority, true),
+            AccountMeta::new(entry, false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new(*rent_recipient, false),
+        ],
+        data: CommunityManagedTokenInstruction::RemoveFromAllowlist.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::AllowlistedTransfer`]
+/// instruction. Both `source_owner` and `destination_owner` must already
+/// have live allowlist entries for `mint`.
+pub fn create_allowlisted_transfer_instruction(
+    source_owner: &Pubkey,
+    destination_owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let (source_entry, _) = AllowlistEntry::find_address(mint, source_owner, &crate::id());
+    let (destination_entry, _) = AllowlistEntry::find_address(mint, destination_owner, &crate::id());
+    let source = get_associated_token_address(source_owner, mint);
+    let destination = get_associated_token_address(destination_owner, mint);
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(source, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(source_entry, false),
+            AccountMeta::new_readonly(destination_entry, false),
+            AccountMeta::new_readonly(*source_owner, true),
+            AccountMeta::new_readonly(*destination_owner, false),
+        ],
+        data: CommunityManagedTokenInstruction::AllowlistedTransfer { amount }.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::InitializeNft`]
+/// instruction.
+pub fn create_initialize_nft_instruction(
+    mint: &Pubkey,
+    community_authority: &Pubkey,
+    payer: &Pubkey,
+    destination_owner: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let destination = get_associated_token_address(destination_owner, mint);
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(*mint, true),
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*community_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(*destination_owner, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(rent::id(), false),
+        ],
+        data: CommunityManagedTokenInstruction::InitializeNft.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::SetMetadata`]
+/// instruction.
+pub fn create_set_metadata_instruction(
+    mint: &Pubkey,
+    community_authority: &Pubkey,
+    payer: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let (metadata, _) = Metadata::find_address(mint, &crate::id());
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*community_authority, true),
+            AccountMeta::new(metadata, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: CommunityManagedTokenInstruction::SetMetadata { name, symbol, uri }.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::BatchTransfer`]
+/// instruction that thaws, transfers, and refreezes every `(source,
+/// dest, amount)` leg atomically. Each `source` owner must sign the
+/// transaction; a single owner may appear in more than one leg.
+pub fn create_batch_transfer_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    transfers: &[(Pubkey, Pubkey, u64)],
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(config, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+    let mut amounts = Vec::with_capacity(transfers.len());
+    for (source_owner, dest_owner, amount) in transfers {
+        accounts.push(AccountMeta::new_readonly(*source_owner, true));
+        accounts.push(AccountMeta::new(
+            get_associated_token_address(source_owner, mint),
+            false,
+        ));
+        accounts.push(AccountMeta::new(
+            get_associated_token_address(dest_owner, mint),
+            false,
+        ));
+        amounts.push(*amount);
+    }
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: CommunityManagedTokenInstruction::BatchTransfer { amounts }.pack(),
+    })
+}
+
+/// Creates a [`CommunityManagedTokenInstruction::Unwrap`] instruction.
+///
+/// Accounts left frozen under the community-managed mint are not thawed
+/// by this instruction; see [`CommunityManagedTokenInstruction::Unwrap`].
+pub fn create_unwrap_instruction(
+    mint: &Pubkey,
+    program_authority: &Pubkey,
+    new_mint_authority: &Pubkey,
+    new_freeze_authority: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = MintConfig::find_address(mint, &crate::id());
+    Ok(Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(config, false),
+            AccountMeta::new(*program_authority, true),
+            AccountMeta::new_readonly(*new_mint_authority, false),
+            AccountMeta::new_readonly(*new_freeze_authority, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: CommunityManagedTokenInstruction::Unwrap.pack(),
+    })
+}
@@ -0,0 +1,22 @@
+//! A community-managed SPL token program.
+//!
+//! Every community-managed mint is controlled by a program-derived
+//! `MintConfig` account (see [`state`]) which doubles as the underlying
+//! `spl_token` mint and freeze authority. Token accounts are kept frozen
+//! at rest and are only thawed for the duration of a single CPI, so that
+//! transfers, mints, and burns all require the community authority's
+//! cooperation.
+
+#![deny(missing_docs)]
+
+pub mod entrypoint;
+pub mod error;
+pub mod instruction;
+pub mod processor;
+pub mod state;
+
+pub use solana_program;
+
+solana_program::declare_id!("AVG7WVZAQ6UDPQkyBiAS57TuwfZkFUZS6cP7b7qDbvHM");
+
+pub use processor::process_instruction;
@@ -0,0 +1,1054 @@
+//! Instruction processing for the community-managed-token program.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::{instructions as instructions_sysvar, Sysvar},
+};
+
+use borsh::BorshSerialize;
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::{
+    error::CommunityManagedTokenError,
+    instruction::CommunityManagedTokenInstruction,
+    state::{AllowlistEntry, Metadata, MintConfig, MAX_NAME_LEN, MAX_SYMBOL_LEN, MAX_URI_LEN},
+};
+
+/// Processes a [`CommunityManagedTokenInstruction`].
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = CommunityManagedTokenInstruction::unpack(instruction_data)?;
+    match instruction {
+        CommunityManagedTokenInstruction::InitializeMint { decimals } => {
+            process_initialize_mint(program_id, accounts, decimals)
+        }
+        CommunityManagedTokenInstruction::InitializeAccount => {
+            process_initialize_account(program_id, accounts)
+        }
+        CommunityManagedTokenInstruction::MintTo { amount } => {
+            process_mint_to(program_id, accounts, amount)
+        }
+        CommunityManagedTokenInstruction::Transfer { amount } => {
+            process_transfer(program_id, accounts, amount)
+        }
+        CommunityManagedTokenInstruction::TransferWithDelegate { amount } => {
+            process_transfer(program_id, accounts, amount)
+        }
+        CommunityManagedTokenInstruction::Approve { amount } => {
+            process_approve(program_id, accounts, amount)
+        }
+        CommunityManagedTokenInstruction::Burn { amount } => {
+            process_burn(program_id, accounts, amount)
+        }
+        CommunityManagedTokenInstruction::Wrap => process_wrap(program_id, accounts),
+        CommunityManagedTokenInstruction::SetAuthorizer { authorizer } => {
+            process_set_authorizer(accounts, authorizer)
+        }
+        CommunityManagedTokenInstruction::TransferWithAuthorization {
+            amount,
+            expiry_slot,
+            nonce,
+        } => process_transfer_with_authorization(program_id, accounts, amount, expiry_slot, nonce),
+        CommunityManagedTokenInstruction::AddToAllowlist => {
+            process_add_to_allowlist(program_id, accounts)
+        }
+        CommunityManagedTokenInstruction::RemoveFromAllowlist => {
+            process_remove_from_allowlist(program_id, accounts)
+        }
+        CommunityManagedTokenInstruction::AllowlistedTransfer { amount } => {
+            process_allowlisted_transfer(program_id, accounts, amount)
+        }
+        CommunityManagedTokenInstruction::InitializeNft => {
+            process_initialize_nft(program_id, accounts)
+        }
+        CommunityManagedTokenInstruction::SetMetadata { name, symbol, uri } => {
+            process_set_metadata(program_id, accounts, name, symbol, uri)
+        }
+        CommunityManagedTokenInstruction::BatchTransfer { amounts } => {
+            process_batch_transfer(program_id, accounts, amounts)
+        }
+        CommunityManagedTokenInstruction::Unwrap => process_unwrap(program_id, accounts),
+    }
+}
+
+fn process_initialize_mint(program_id: &Pubkey, accounts: &[AccountInfo], decimals: u8) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let community_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    let rent = &Rent::from_account_info(rent_info)?;
+    let (config_address, bump_seed) = MintConfig::find_address(mint_info.key, program_id);
+    if config_address != *config_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    invoke(
+        &system_instruction::create_account(
+            payer_info.key,
+            mint_info.key,
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        &[payer_info.clone(), mint_info.clone()],
+    )?;
+
+    create_pda_account(
+        payer_info,
+        config_info,
+        rent,
+        MintConfig::LEN,
+        program_id,
+        &MintConfig::signer_seeds(mint_info.key, &[bump_seed]),
+    )?;
+
+    invoke(
+        &spl_token::instruction::initialize_mint2(
+            &spl_token::id(),
+            mint_info.key,
+            &config_address,
+            Some(&config_address),
+            decimals,
+        )?,
+        &[mint_info.clone()],
+    )?;
+
+    let config = MintConfig {
+        is_initialized: true,
+        mint: *mint_info.key,
+        authority: *community_authority_info.key,
+        authorizer: [0u8; 20],
+        bump_seed,
+    };
+    config.pack_into_slice(&mut config_info.data.borrow_mut());
+
+    let _ = token_program_info;
+    Ok(())
+}
+
+fn process_initialize_account(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let community_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let associated_token_program_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    let config = load_config(config_info, mint_info.key, program_id)?;
+    require_authority_signer(community_authority_info, &config)?;
+
+    invoke(
+        &spl_associated_token_account::instruction::create_associated_token_account(
+            payer_info.key,
+            owner_info.key,
+            mint_info.key,
+        ),
+        &[
+            payer_info.clone(),
+            account_info.clone(),
+            owner_info.clone(),
+            mint_info.clone(),
+            system_program_info.clone(),
+            token_program_info.clone(),
+            associated_token_program_info.clone(),
+            rent_info.clone(),
+        ],
+    )?;
+
+    let bump_seed = [config.bump_seed];
+    let seeds = MintConfig::signer_seeds(mint_info.key, &bump_seed);
+    invoke_signed(
+        &spl_token::instruction::freeze_account(
+            &spl_token::id(),
+            account_info.key,
+            mint_info.key,
+            config_info.key,
+            &[],
+        )?,
+        &[account_info.clone(), mint_info.clone(), config_info.clone()],
+        &[&seeds],
+    )
+}
+
+fn process_mint_to(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let community_authority_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+
+    let config = load_config(config_info, mint_info.key, program_id)?;
+    require_authority_signer(community_authority_info, &config)?;
+
+    let bump_seed = [config.bump_seed];
+    let seeds = MintConfig::signer_seeds(mint_info.key, &bump_seed);
+
+    thaw(mint_info, destination_info, config_info, &seeds)?;
+    invoke_signed(
+        &spl_token::instruction::mint_to(
+            &spl_token::id(),
+            mint_info.key,
+            destination_info.key,
+            config_info.key,
+            &[],
+            amount,
+        )?,
+        &[mint_info.clone(), destination_info.clone(), config_info.clone()],
+        &[&seeds],
+    )?;
+    freeze(mint_info, destination_info, config_info, &seeds)
+}
+
+fn process_transfer(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let source_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let authorizer_info = next_account_info(account_info_iter)?;
+    let community_authority_info = next_account_info(account_info_iter)?;
+
+    let config = load_config(config_info, mint_info.key, program_id)?;
+    require_authority_signer(community_authority_info, &config)?;
+    if !authorizer_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let bump_seed = [config.bump_seed];
+    let seeds = MintConfig::signer_seeds(mint_info.key, &bump_seed);
+
+    thaw(mint_info, source_info, config_info, &seeds)?;
+    thaw(mint_info, destination_info, config_info, &seeds)?;
+    invoke(
+        &spl_token::instruction::transfer(
+            &spl_token::id(),
+            source_info.key,
+            destination_info.key,
+            authorizer_info.key,
+            &[],
+            amount,
+        )?,
+        &[source_info.clone(), destination_info.clone(), authorizer_info.clone()],
+    )?;
+    freeze(mint_info, source_info, config_info, &seeds)?;
+    freeze(mint_info, destination_info, config_info, &seeds)
+}
+
+fn process_approve(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let source_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let community_authority_info = next_account_info(account_info_iter)?;
+    let delegate_info = next_account_info(account_info_iter)?;
+
+    let config = load_config(config_info, mint_info.key, program_id)?;
+    require_authority_signer(community_authority_info, &config)?;
+    if !owner_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let bump_seed = [config.bump_seed];
+    let seeds = MintConfig::signer_seeds(mint_info.key, &bump_seed);
+
+    thaw(mint_info, source_info, config_info, &seeds)?;
+    invoke(
+        &spl_token::instruction::approve(
+            &spl_token::id(),
+            source_info.key,
+            delegate_info.key,
+            owner_info.key,
+            &[],
+            amount,
+        )?,
+        &[source_info.clone(), delegate_info.clone(), owner_info.clone()],
+    )?;
+    freeze(mint_info, source_info, config_info, &seeds)
+}
+
+fn process_burn(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let community_authority_info = next_account_info(account_info_iter)?;
+
+    let config = load_config(config_info, mint_info.key, program_id)?;
+    require_authority_signer(community_authority_info, &config)?;
+    if !owner_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let bump_seed = [config.bump_seed];
+    let seeds = MintConfig::signer_seeds(mint_info.key, &bump_seed);
+
+    thaw(mint_info, account_info, config_info, &seeds)?;
+    invoke(
+        &spl_token::instruction::burn(
+            &spl_token::id(),
+            account_info.key,
+            mint_info.key,
+            owner_info.key,
+            &[],
+            amount,
+        )?,
+        &[account_info.clone(), mint_info.clone(), owner_info.clone()],
+    )?;
+    freeze(mint_info, account_info, config_info, &seeds)
+}
+
+fn process_wrap(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let current_mint_authority_info = next_account_info(account_info_iter)?;
+    let current_freeze_authority_info = next_account_info(account_info_iter)?;
+    let new_community_authority_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let _token_program_info = next_account_info(account_info_iter)?;
+
+    if !current_mint_authority_info.is_signer || !current_freeze_authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (config_address, bump_seed) = MintConfig::find_address(mint_info.key, program_id);
+    if config_address != *config_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let rent = Rent::get()?;
+    create_pda_account(
+        current_mint_authority_info,
+        config_info,
+        &rent,
+        MintConfig::LEN,
+        program_id,
+        &MintConfig::signer_seeds(mint_info.key, &[bump_seed]),
+    )?;
+
+    invoke(
+        &spl_token::instruction::set_authority(
+            &spl_token::id(),
+            mint_info.key,
+            Some(&config_address),
+            spl_token::instruction::AuthorityType::MintTokens,
+            current_mint_authority_info.key,
+            &[],
+        )?,
+        &[mint_info.clone(), current_mint_authority_info.clone()],
+    )?;
+    invoke(
+        &spl_token::instruction::set_authority(
+            &spl_token::id(),
+            mint_info.key,
+            Some(&config_address),
+            spl_token::instruction::AuthorityType::FreezeAccount,
+            current_freeze_authority_info.key,
+            &[],
+        )?,
+        &[mint_info.clone(), current_freeze_authority_info.clone()],
+    )?;
+
+    let config = MintConfig {
+        is_initialized: true,
+        mint: *mint_info.key,
+        authority: *new_community_authority_info.key,
+        authorizer: [0u8; 20],
+        bump_seed,
+    };
+    config.pack_into_slice(&mut config_info.data.borrow_mut());
+    Ok(())
+}
+
+fn process_set_authorizer(accounts: &[AccountInfo], authorizer: [u8; 20]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_info = next_account_info(account_info_iter)?;
+    let community_authority_info = next_account_info(account_info_iter)?;
+
+    let mut config = MintConfig::unpack(&config_info.data.borrow())?;
+    require_authority_signer(community_authority_info, &config)?;
+
+    config.authorizer = authorizer;
+    config.pack_into_slice(&mut config_info.data.borrow_mut());
+    Ok(())
+}
+
+fn process_transfer_with_authorization(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    expiry_slot: u64,
+    nonce: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let source_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let nonce_marker_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let _token_program_info = next_account_info(account_info_iter)?;
+    let instructions_info = next_account_info(account_info_iter)?;
+
+    if !owner_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let config = load_config(config_info, mint_info.key, program_id)?;
+
+    if Clock::get()?.slot > expiry_slot {
+        return Err(CommunityManagedTokenError::AuthorizationExpired.into());
+    }
+
+    let destination_owner =
+        spl_token::state::Account::unpack(&destination_info.data.borrow())?.owner;
+
+    let mut message = Vec::with_capacity(32 * 3 + 8 + 8 + 8);
+    message.extend_from_slice(owner_info.key.as_ref());
+    message.extend_from_slice(destination_owner.as_ref());
+    message.extend_from_slice(mint_info.key.as_ref());
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(&expiry_slot.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+
+    let signer = recover_secp256k1_signer(instructions_info, &message)?;
+    if signer != config.authorizer {
+        return Err(CommunityManagedTokenError::AuthorizerMismatch.into());
+    }
+
+    let (nonce_address, nonce_bump) = MintConfig::find_nonce_address(mint_info.key, nonce, program_id);
+    if nonce_address != *nonce_marker_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if nonce_marker_info.owner == program_id {
+        return Err(CommunityManagedTokenError::NonceAlreadyUsed.into());
+    }
+
+    let rent = Rent::get()?;
+    let nonce_bytes = nonce.to_le_bytes();
+    create_pda_account(
+        owner_info,
+        nonce_marker_info,
+        &rent,
+        0,
+        program_id,
+        &[
+            crate::state::NONCE_SEED,
+            mint_info.key.as_ref(),
+            &nonce_bytes,
+            &[nonce_bump],
+        ],
+    )?;
+
+    let bump_seed = [config.bump_seed];
+    let seeds = MintConfig::signer_seeds(mint_info.key, &bump_seed);
+
+    thaw(mint_info, source_info, config_info, &seeds)?;
+    thaw(mint_info, destination_info, config_info, &seeds)?;
+    invoke(
+        &spl_token::instruction::transfer(
+            &spl_token::id(),
+            source_info.key,
+            destination_info.key,
+            owner_info.key,
+            &[],
+            amount,
+        )?,
+        &[source_info.clone(), destination_info.clone(), owner_info.clone()],
+    )?;
+    freeze(mint_info, source_info, config_info, &seeds)?;
+    freeze(mint_info, destination_info, config_info, &seeds)
+}
+
+fn process_add_to_allowlist(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let community_authority_info = next_account_info(account_info_iter)?;
+    let entry_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    let config = load_config(config_info, mint_info.key, program_id)?;
+    require_authority_signer(community_authority_info, &config)?;
+
+    let (entry_address, bump_seed) = AllowlistEntry::find_address(mint_info.key, owner_info.key, program_id);
+    if entry_address != *entry_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let rent = Rent::get()?;
+    create_pda_account(
+        payer_info,
+        entry_info,
+        &rent,
+        0,
+        program_id,
+        &[
+            crate::state::ALLOWLIST_SEED,
+            mint_info.key.as_ref(),
+            owner_info.key.as_ref(),
+            &[bump_seed],
+        ],
+    )
+}
+
+fn process_remove_from_allowlist(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let community_authority_info = next_account_info(account_info_iter)?;
+    let entry_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let rent_recipient_info = next_account_info(account_info_iter)?;
+
+    let config = load_config(config_info, mint_info.key, program_id)?;
+    require_authority_signer(community_authority_info, &config)?;
+
+    let (entry_address, _) = AllowlistEntry::find_address(mint_info.key, owner_info.key, program_id);
+    if entry_address != *entry_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if entry_info.owner != program_id {
+        return Err(CommunityManagedTokenError::NotAllowlisted.into());
+    }
+
+    let recipient_starting_lamports = rent_recipient_info.lamports();
+    **rent_recipient_info.lamports.borrow_mut() =
+        recipient_starting_lamports.saturating_add(entry_info.lamports());
+    **entry_info.lamports.borrow_mut() = 0;
+    entry_info.assign(&solana_program::system_program::id());
+    entry_info.realloc(0, false)
+}
+
+fn process_allowlisted_transfer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let source_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let source_entry_info = next_account_info(account_info_iter)?;
+    let destination_entry_info = next_account_info(account_info_iter)?;
+    let source_owner_info = next_account_info(account_info_iter)?;
+    let destination_owner_info = next_account_info(account_info_iter)?;
+
+    if !source_owner_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *source_info.key != get_associated_token_address(source_owner_info.key, mint_info.key)
+        || *destination_info.key
+            != get_associated_token_address(destination_owner_info.key, mint_info.key)
+    {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let config = load_config(config_info, mint_info.key, program_id)?;
+    require_allowlisted(source_entry_info, mint_info.key, source_owner_info.key, program_id)?;
+    require_allowlisted(
+        destination_entry_info,
+        mint_info.key,
+        destination_owner_info.key,
+        program_id,
+    )?;
+
+    let bump_seed = [config.bump_seed];
+    let seeds = MintConfig::signer_seeds(mint_info.key, &bump_seed);
+
+    thaw(mint_info, source_info, config_info, &seeds)?;
+    thaw(mint_info, destination_info, config_info, &seeds)?;
+    invoke(
+        &spl_token::instruction::transfer(
+            &spl_token::id(),
+            source_info.key,
+            destination_info.key,
+            source_owner_info.key,
+            &[],
+            amount,
+        )?,
+        &[
+            source_info.clone(),
+            destination_info.clone(),
+            source_owner_info.clone(),
+        ],
+    )?;
+    freeze(mint_info, source_info, config_info, &seeds)?;
+    freeze(mint_info, destination_info, config_info, &seeds)
+}
+
+/// Checks that `entry_info` is the live allowlist entry for
+/// `(mint, owner)`.
+fn require_allowlisted(
+    entry_info: &AccountInfo,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let (entry_address, _) = AllowlistEntry::find_address(mint, owner, program_id);
+    if entry_address != *entry_info.key || entry_info.owner != program_id {
+        return Err(CommunityManagedTokenError::NotAllowlisted.into());
+    }
+    Ok(())
+}
+
+fn process_initialize_nft(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let community_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let destination_owner_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let associated_token_program_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    let rent = &Rent::from_account_info(rent_info)?;
+    let (config_address, bump_seed) = MintConfig::find_address(mint_info.key, program_id);
+    if config_address != *config_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    invoke(
+        &system_instruction::create_account(
+            payer_info.key,
+            mint_info.key,
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        &[payer_info.clone(), mint_info.clone()],
+    )?;
+
+    create_pda_account(
+        payer_info,
+        config_info,
+        rent,
+        MintConfig::LEN,
+        program_id,
+        &MintConfig::signer_seeds(mint_info.key, &[bump_seed]),
+    )?;
+
+    invoke(
+        &spl_token::instruction::initialize_mint2(
+            &spl_token::id(),
+            mint_info.key,
+            &config_address,
+            Some(&config_address),
+            0,
+        )?,
+        &[mint_info.clone()],
+    )?;
+
+    invoke(
+        &spl_associated_token_account::instruction::create_associated_token_account(
+            payer_info.key,
+            destination_owner_info.key,
+            mint_info.key,
+        ),
+        &[
+            payer_info.clone(),
+            destination_info.clone(),
+            destination_owner_info.clone(),
+            mint_info.clone(),
+            system_program_info.clone(),
+            token_program_info.clone(),
+            associated_token_program_info.clone(),
+            rent_info.clone(),
+        ],
+    )?;
+
+    let bump_seed_bytes = [bump_seed];
+    let seeds = MintConfig::signer_seeds(mint_info.key, &bump_seed_bytes);
+    invoke_signed(
+        &spl_token::instruction::mint_to(
+            &spl_token::id(),
+            mint_info.key,
+            destination_info.key,
+            &config_address,
+            &[],
+            1,
+        )?,
+        &[mint_info.clone(), destination_info.clone(), config_info.clone()],
+        &[&seeds],
+    )?;
+
+    // A one-time mint: revoke minting authority for good.
+    invoke_signed(
+        &spl_token::instruction::set_authority(
+            &spl_token::id(),
+            mint_info.key,
+            None,
+            spl_token::instruction::AuthorityType::MintTokens,
+            &config_address,
+            &[],
+        )?,
+        &[mint_info.clone(), config_info.clone()],
+        &[&seeds],
+    )?;
+
+    freeze(mint_info, destination_info, config_info, &seeds)?;
+
+    let config = MintConfig {
+        is_initialized: true,
+        mint: *mint_info.key,
+        authority: *community_authority_info.key,
+        authorizer: [0u8; 20],
+        bump_seed,
+    };
+    config.pack_into_slice(&mut config_info.data.borrow_mut());
+    Ok(())
+}
+
+fn process_set_metadata(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+    symbol: String,
+    uri: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let community_authority_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    if name.len() > MAX_NAME_LEN || symbol.len() > MAX_SYMBOL_LEN || uri.len() > MAX_URI_LEN {
+        return Err(CommunityManagedTokenError::MetadataTooLong.into());
+    }
+
+    let config = load_config(config_info, mint_info.key, program_id)?;
+    require_authority_signer(community_authority_info, &config)?;
+
+    let (metadata_address, bump_seed) = Metadata::find_address(mint_info.key, program_id);
+    if metadata_address != *metadata_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if metadata_info.owner != program_id {
+        let rent = Rent::get()?;
+        create_pda_account(
+            payer_info,
+            metadata_info,
+            &rent,
+            Metadata::LEN,
+            program_id,
+            &[
+                crate::state::METADATA_SEED,
+                mint_info.key.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+    }
+
+    let metadata = Metadata {
+        is_initialized: true,
+        mint: *mint_info.key,
+        name,
+        symbol,
+        uri,
+    };
+    metadata
+        .serialize(&mut &mut metadata_info.data.borrow_mut()[..])
+        .map_err(|_| ProgramError::AccountDataTooSmall)?;
+    Ok(())
+}
+
+fn process_batch_transfer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amounts: Vec<u64>,
+) -> ProgramResult {
+    if amounts.is_empty() {
+        return Err(CommunityManagedTokenError::InvalidBatch.into());
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    let config = load_config(config_info, mint_info.key, program_id)?;
+    require_authority_signer(authority_info, &config)?;
+
+    let bump_seed = [config.bump_seed];
+    let seeds = MintConfig::signer_seeds(mint_info.key, &bump_seed);
+
+    for amount in amounts {
+        let owner_info = next_account_info(account_info_iter)?;
+        let source_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+
+        if !owner_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        thaw(mint_info, source_info, config_info, &seeds)?;
+        thaw(mint_info, destination_info, config_info, &seeds)?;
+        invoke(
+            &spl_token::instruction::transfer(
+                &spl_token::id(),
+                source_info.key,
+                destination_info.key,
+                owner_info.key,
+                &[],
+                amount,
+            )?,
+            &[
+                source_info.clone(),
+                destination_info.clone(),
+                owner_info.clone(),
+            ],
+        )?;
+        freeze(mint_info, source_info, config_info, &seeds)?;
+        freeze(mint_info, destination_info, config_info, &seeds)?;
+    }
+
+    if account_info_iter.next().is_some() {
+        return Err(CommunityManagedTokenError::InvalidBatch.into());
+    }
+    Ok(())
+}
+
+fn process_unwrap(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let community_authority_info = next_account_info(account_info_iter)?;
+    let new_mint_authority_info = next_account_info(account_info_iter)?;
+    let new_freeze_authority_info = next_account_info(account_info_iter)?;
+
+    let config = load_config(config_info, mint_info.key, program_id)?;
+    require_authority_signer(community_authority_info, &config)?;
+
+    let bump_seed = [config.bump_seed];
+    let seeds = MintConfig::signer_seeds(mint_info.key, &bump_seed);
+
+    invoke_signed(
+        &spl_token::instruction::set_authority(
+            &spl_token::id(),
+            mint_info.key,
+            Some(new_mint_authority_info.key),
+            spl_token::instruction::AuthorityType::MintTokens,
+            config_info.key,
+            &[],
+        )?,
+        &[mint_info.clone(), config_info.clone()],
+        &[&seeds],
+    )?;
+    invoke_signed(
+        &spl_token::instruction::set_authority(
+            &spl_token::id(),
+            mint_info.key,
+            Some(new_freeze_authority_info.key),
+            spl_token::instruction::AuthorityType::FreezeAccount,
+            config_info.key,
+            &[],
+        )?,
+        &[mint_info.clone(), config_info.clone()],
+        &[&seeds],
+    )?;
+
+    // The mint is a standard spl_token mint again; close its now-unused
+    // MintConfig and return the rent to the community authority.
+    let authority_starting_lamports = community_authority_info.lamports();
+    **community_authority_info.lamports.borrow_mut() =
+        authority_starting_lamports.saturating_add(config_info.lamports());
+    **config_info.lamports.borrow_mut() = 0;
+    config_info.assign(&solana_program::system_program::id());
+    config_info.realloc(0, false)
+}
+
+/// Loads and validates a mint's `MintConfig` account.
+fn load_config(
+    config_info: &AccountInfo,
+    mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<MintConfig, ProgramError> {
+    let (config_address, _) = MintConfig::find_address(mint, program_id);
+    if config_address != *config_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let config = MintConfig::unpack(&config_info.data.borrow())?;
+    if config.mint != *mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(config)
+}
+
+/// Checks that `authority_info` is a signer matching `config.authority`.
+fn require_authority_signer(authority_info: &AccountInfo, config: &MintConfig) -> ProgramResult {
+    if !authority_info.is_signer || *authority_info.key != config.authority {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+fn thaw<'a>(
+    mint_info: &AccountInfo<'a>,
+    account_info: &AccountInfo<'a>,
+    config_info: &AccountInfo<'a>,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &spl_token::instruction::thaw_account(
+            &spl_token::id(),
+            account_info.key,
+            mint_info.key,
+            config_info.key,
+            &[],
+        )?,
+        &[account_info.clone(), mint_info.clone(), config_info.clone()],
+        &[seeds],
+    )
+}
+
+fn freeze<'a>(
+    mint_info: &AccountInfo<'a>,
+    account_info: &AccountInfo<'a>,
+    config_info: &AccountInfo<'a>,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &spl_token::instruction::freeze_account(
+            &spl_token::id(),
+            account_info.key,
+            mint_info.key,
+            config_info.key,
+            &[],
+        )?,
+        &[account_info.clone(), mint_info.clone(), config_info.clone()],
+        &[seeds],
+    )
+}
+
+/// Creates and allocates a program-owned PDA, signing with `seeds`.
+fn create_pda_account<'a>(
+    payer_info: &AccountInfo<'a>,
+    pda_info: &AccountInfo<'a>,
+    rent: &Rent,
+    space: usize,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &system_instruction::create_account(
+            payer_info.key,
+            pda_info.key,
+            rent.minimum_balance(space),
+            space as u64,
+            owner,
+        ),
+        &[payer_info.clone(), pda_info.clone()],
+        &[seeds],
+    )
+}
+
+/// Recovers the secp256k1 signer of the `secp256k1_program` instruction
+/// immediately preceding this one, and checks it signed over `message`.
+///
+/// This mirrors the guardian-signature verification pattern used by the
+/// Wormhole token bridge: the client places a `secp256k1_program`
+/// instruction directly before this one, and the program recovers the
+/// expected signer via instruction introspection rather than requiring
+/// an on-chain ed25519 co-signature.
+fn recover_secp256k1_signer(
+    instructions_info: &AccountInfo,
+    message: &[u8],
+) -> Result<[u8; 20], ProgramError> {
+    let current_index = instructions_sysvar::load_current_index_checked(instructions_info)?;
+    if current_index == 0 {
+        return Err(CommunityManagedTokenError::MissingSecp256k1Instruction.into());
+    }
+    let secp_ix =
+        instructions_sysvar::load_instruction_at_checked((current_index - 1) as usize, instructions_info)?;
+    if secp_ix.program_id != solana_program::secp256k1_program::id() {
+        return Err(CommunityManagedTokenError::MissingSecp256k1Instruction.into());
+    }
+
+    // secp256k1 instruction data layout: a 1-byte signature count
+    // immediately followed by one `SecpSignatureOffsets` struct (11
+    // bytes: signature_offset: u16, signature_instruction_index: u8,
+    // eth_address_offset: u16, eth_address_instruction_index: u8,
+    // message_data_offset: u16, message_data_size: u16,
+    // message_instruction_index: u8) per signature -- there is no
+    // padding byte after the count. We only support (and require) a
+    // single signature, laid out by the signer in the same instruction
+    // as its eth address and message.
+    let data = &secp_ix.data;
+    let num_signatures = *data.first().ok_or(CommunityManagedTokenError::MissingSecp256k1Instruction)?;
+    if num_signatures != 1 || data.len() < 1 + 11 {
+        return Err(CommunityManagedTokenError::MissingSecp256k1Instruction.into());
+    }
+    let offsets = &data[1..12];
+    let eth_address_offset = u16::from_le_bytes([offsets[3], offsets[4]]) as usize;
+    let message_data_offset = u16::from_le_bytes([offsets[6], offsets[7]]) as usize;
+    let message_data_size = u16::from_le_bytes([offsets[8], offsets[9]]) as usize;
+
+    // Every offset is only meaningful relative to the instruction it
+    // names. A secp256k1 instruction can reference *another*
+    // instruction's signature/address/message bytes by index, which lets
+    // the precompile verify a signature that lives elsewhere in the
+    // transaction -- that other instruction's bytes are not the ones we
+    // read below, so if any index field doesn't point back at this same
+    // secp256k1 instruction, the data we're about to read was never
+    // actually checked against the signature the precompile verified.
+    let secp_ix_index = current_index - 1;
+    let signature_instruction_index = offsets[2];
+    let eth_address_instruction_index = offsets[5];
+    let message_instruction_index = offsets[10];
+    if signature_instruction_index as u16 != secp_ix_index
+        || eth_address_instruction_index as u16 != secp_ix_index
+        || message_instruction_index as u16 != secp_ix_index
+    {
+        return Err(CommunityManagedTokenError::MissingSecp256k1Instruction.into());
+    }
+
+    let eth_address = data
+        .get(eth_address_offset..eth_address_offset + 20)
+        .ok_or(CommunityManagedTokenError::MissingSecp256k1Instruction)?;
+    let signed_message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(CommunityManagedTokenError::MissingSecp256k1Instruction)?;
+
+    if signed_message != message {
+        msg!("secp256k1 message does not match the expected authorization payload");
+        return Err(CommunityManagedTokenError::AuthorizerMismatch.into());
+    }
+
+    let mut signer = [0u8; 20];
+    signer.copy_from_slice(eth_address);
+    Ok(signer)
+}
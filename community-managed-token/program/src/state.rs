@@ -0,0 +1,170 @@
+//! On-chain account layouts for the community-managed-token program.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// Seed prefix for a mint's [`MintConfig`] PDA.
+pub const MINT_CONFIG_SEED: &[u8] = b"config";
+
+/// Seed prefix for a consumed-nonce marker PDA.
+pub const NONCE_SEED: &[u8] = b"nonce";
+
+/// Seed prefix for an [`AllowlistEntry`] PDA.
+pub const ALLOWLIST_SEED: &[u8] = b"allowlist";
+
+/// Per-mint configuration, seeded `["config", mint]`.
+///
+/// This account is the `spl_token` mint and freeze authority for its
+/// mint: the program signs for it with `invoke_signed` so that transfers,
+/// mints, and burns can thaw and refreeze accounts on the community's
+/// behalf. `authority` is the ed25519 keypair that must co-sign ordinary
+/// community-managed instructions; `authorizer` is an independent
+/// secp256k1 key (a 20-byte Ethereum-style address) that may instead
+/// approve individual transfers off-chain, see
+/// [`crate::instruction::CommunityManagedTokenInstruction::TransferWithAuthorization`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MintConfig {
+    /// Whether this account has been initialized.
+    pub is_initialized: bool,
+    /// The mint this configuration applies to.
+    pub mint: Pubkey,
+    /// The community authority that must co-sign ordinary transfers,
+    /// mints, burns, and approvals.
+    pub authority: Pubkey,
+    /// The secp256k1 signer authorized to approve off-chain transfers
+    /// for this mint, or `[0; 20]` if none has been set.
+    pub authorizer: [u8; 20],
+    /// The bump seed for this account's own PDA, reused to sign CPIs as
+    /// the mint and freeze authority.
+    pub bump_seed: u8,
+}
+
+impl Sealed for MintConfig {}
+
+impl IsInitialized for MintConfig {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for MintConfig {
+    const LEN: usize = 1 + 32 + 32 + 20 + 1;
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let is_initialized = match src[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let mint = Pubkey::new(&src[1..33]);
+        let authority = Pubkey::new(&src[33..65]);
+        let mut authorizer = [0u8; 20];
+        authorizer.copy_from_slice(&src[65..85]);
+        let bump_seed = src[85];
+        Ok(Self {
+            is_initialized,
+            mint,
+            authority,
+            authorizer,
+            bump_seed,
+        })
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        dst[0] = self.is_initialized as u8;
+        dst[1..33].copy_from_slice(self.mint.as_ref());
+        dst[33..65].copy_from_slice(self.authority.as_ref());
+        dst[65..85].copy_from_slice(&self.authorizer);
+        dst[85] = self.bump_seed;
+    }
+}
+
+impl MintConfig {
+    /// Derives the `MintConfig` PDA address and bump seed for `mint`.
+    pub fn find_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[MINT_CONFIG_SEED, mint.as_ref()], program_id)
+    }
+
+    /// The seeds needed to sign a CPI as this account's PDA.
+    pub fn signer_seeds<'a>(mint: &'a Pubkey, bump_seed: &'a [u8; 1]) -> [&'a [u8]; 3] {
+        [MINT_CONFIG_SEED, mint.as_ref(), bump_seed]
+    }
+
+    /// Derives the consumed-nonce marker PDA for `(mint, nonce)`.
+    ///
+    /// The marker account carries no data; its mere existence, owned by
+    /// this program, is the replay-protection record.
+    pub fn find_nonce_address(mint: &Pubkey, nonce: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[NONCE_SEED, mint.as_ref(), &nonce.to_le_bytes()],
+            program_id,
+        )
+    }
+}
+
+/// Marks `(mint, owner)` as allowlisted, seeded `["allowlist", mint,
+/// owner]`.
+///
+/// The account carries no data; its existence, owned by this program, is
+/// the allowlist record. A pre-vetted member can only use
+/// [`crate::instruction::CommunityManagedTokenInstruction::AllowlistedTransfer`]
+/// once both the source and destination owners each have a live entry.
+pub struct AllowlistEntry;
+
+impl AllowlistEntry {
+    /// Derives the allowlist entry PDA for `(mint, owner)`.
+    pub fn find_address(mint: &Pubkey, owner: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[ALLOWLIST_SEED, mint.as_ref(), owner.as_ref()],
+            program_id,
+        )
+    }
+}
+
+/// Seed prefix for a [`Metadata`] PDA.
+pub const METADATA_SEED: &[u8] = b"metadata";
+
+/// Maximum length of [`Metadata::name`], in bytes.
+pub const MAX_NAME_LEN: usize = 32;
+/// Maximum length of [`Metadata::symbol`], in bytes.
+pub const MAX_SYMBOL_LEN: usize = 10;
+/// Maximum length of [`Metadata::uri`], in bytes.
+pub const MAX_URI_LEN: usize = 200;
+
+/// On-chain name/symbol/uri for a community-managed NFT mint, seeded
+/// `["metadata", mint]`.
+///
+/// Unlike the rest of this program's accounts, `Metadata` is
+/// Borsh-serialized so its variable-length string fields don't need a
+/// hand-rolled layout. The account is sized to the maximum possible
+/// length up front so it never needs to be reallocated on update.
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Metadata {
+    /// Whether this account has been initialized.
+    pub is_initialized: bool,
+    /// The mint this metadata describes.
+    pub mint: Pubkey,
+    /// Display name of the NFT.
+    pub name: String,
+    /// Ticker-style symbol of the NFT.
+    pub symbol: String,
+    /// URI pointing at the NFT's off-chain (or data://) content.
+    pub uri: String,
+}
+
+impl Metadata {
+    /// The account size that fits the maximum length of every field.
+    pub const LEN: usize = 1 + 32 + (4 + MAX_NAME_LEN) + (4 + MAX_SYMBOL_LEN) + (4 + MAX_URI_LEN);
+
+    /// Derives the `Metadata` PDA address and bump seed for `mint`.
+    pub fn find_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[METADATA_SEED, mint.as_ref()], program_id)
+    }
+}
@@ -1,10 +1,12 @@
-use solana_program::program_pack::Pack;
+use borsh::BorshDeserialize;
+use solana_program::{clock::Clock, program_pack::Pack, secp256k1_program};
 use solana_program_test::*;
 use solana_sdk::{
     commitment_config::CommitmentLevel,
-    instruction::Instruction,
+    instruction::{AccountMeta, Instruction},
     native_token::LAMPORTS_PER_SOL,
     pubkey::Pubkey,
+    secp256k1_instruction::{construct_eth_pubkey, new_secp256k1_instruction},
     signature::Signature,
     signature::{Keypair, Signer},
     system_instruction,
@@ -12,6 +14,7 @@ use solana_sdk::{
 };
 
 use community_managed_token::instruction::*;
+use community_managed_token::state::Metadata;
 use spl_associated_token_account::{
     get_associated_token_address, instruction::create_associated_token_account,
 };
@@ -248,6 +251,268 @@ async fn test_community_managed_token_with_delegate() {
     .unwrap();
 }
 
+#[tokio::test]
+async fn test_community_managed_token_transfer_with_authorization() {
+    let mut context = community_managed_token_test().start_with_context().await;
+    let lwc = &mut context.banks_client;
+    let authority = Keypair::new();
+    transfer(lwc, &context.payer, &authority.pubkey(), sol(10.0))
+        .await
+        .unwrap();
+    let mint = Keypair::new();
+    let mint_key = mint.pubkey();
+    let create_ix =
+        create_initialize_mint_instruction(&mint_key, &authority.pubkey(), &authority.pubkey(), 0)
+            .unwrap();
+    process_transaction(lwc, vec![create_ix], vec![&authority, &mint])
+        .await
+        .unwrap();
+
+    let alice = Keypair::new();
+    let alice_key = alice.pubkey();
+    let bob = Keypair::new();
+    let bob_key = bob.pubkey();
+
+    for k in [&alice_key, &bob_key] {
+        transfer(lwc, &context.payer, k, sol(1.0)).await.unwrap();
+        let create_ata = create_initialize_account_instruction(
+            &mint_key,
+            k,
+            &authority.pubkey(),
+            &authority.pubkey(),
+        )
+        .unwrap();
+        process_transaction(lwc, vec![create_ata], vec![&authority])
+            .await
+            .unwrap();
+    }
+    let mint_to_ix =
+        create_mint_to_instruction(&mint_key, &alice_key, &authority.pubkey(), 1000).unwrap();
+    process_transaction(lwc, vec![mint_to_ix], vec![&authority])
+        .await
+        .unwrap();
+
+    let authorizer_key = libsecp256k1::SecretKey::parse(&[7u8; 32]).unwrap();
+    let authorizer_address =
+        construct_eth_pubkey(&libsecp256k1::PublicKey::from_secret_key(&authorizer_key));
+    let set_authorizer_ix =
+        create_set_authorizer_instruction(&mint_key, &authority.pubkey(), authorizer_address)
+            .unwrap();
+    process_transaction(lwc, vec![set_authorizer_ix], vec![&authority])
+        .await
+        .unwrap();
+
+    let expiry_slot = lwc.get_sysvar::<Clock>().await.unwrap().slot + 1_000;
+
+    let authorization_message = |nonce: u64| -> Vec<u8> {
+        let mut message = Vec::with_capacity(32 * 3 + 8 + 8 + 8);
+        message.extend_from_slice(alice_key.as_ref());
+        message.extend_from_slice(bob_key.as_ref());
+        message.extend_from_slice(mint_key.as_ref());
+        message.extend_from_slice(&100u64.to_le_bytes());
+        message.extend_from_slice(&expiry_slot.to_le_bytes());
+        message.extend_from_slice(&nonce.to_le_bytes());
+        message
+    };
+
+    // An authorization signed by a key other than the mint's configured
+    // authorizer must be rejected.
+    let wrong_key = libsecp256k1::SecretKey::parse(&[9u8; 32]).unwrap();
+    let wrong_signer_ix = new_secp256k1_instruction(&wrong_key, &authorization_message(1));
+    let wrong_signer_transfer_ix = create_transfer_with_authorization_instruction(
+        &alice_key,
+        &bob_key,
+        &mint_key,
+        100,
+        expiry_slot,
+        1,
+    )
+    .unwrap();
+    assert!(process_transaction(
+        lwc,
+        vec![wrong_signer_ix, wrong_signer_transfer_ix],
+        vec![&alice],
+    )
+    .await
+    .is_err());
+
+    // An authorization whose expiry slot has already passed must be
+    // rejected, even if it is otherwise correctly signed.
+    let expired_message = {
+        let mut message = Vec::with_capacity(32 * 3 + 8 + 8 + 8);
+        message.extend_from_slice(alice_key.as_ref());
+        message.extend_from_slice(bob_key.as_ref());
+        message.extend_from_slice(mint_key.as_ref());
+        message.extend_from_slice(&100u64.to_le_bytes());
+        message.extend_from_slice(&0u64.to_le_bytes());
+        message.extend_from_slice(&1u64.to_le_bytes());
+        message
+    };
+    let expired_secp_ix = new_secp256k1_instruction(&authorizer_key, &expired_message);
+    let expired_transfer_ix =
+        create_transfer_with_authorization_instruction(&alice_key, &bob_key, &mint_key, 100, 0, 1)
+            .unwrap();
+    assert!(
+        process_transaction(lwc, vec![expired_secp_ix, expired_transfer_ix], vec![&alice])
+            .await
+            .is_err()
+    );
+
+    // A correctly signed, unexpired authorization succeeds without the
+    // community authority as a co-signer.
+    let secp_ix = new_secp256k1_instruction(&authorizer_key, &authorization_message(1));
+    let transfer_ix = create_transfer_with_authorization_instruction(
+        &alice_key,
+        &bob_key,
+        &mint_key,
+        100,
+        expiry_slot,
+        1,
+    )
+    .unwrap();
+    process_transaction(lwc, vec![secp_ix, transfer_ix], vec![&alice])
+        .await
+        .unwrap();
+    assert_eq!(
+        token_balance(lwc, &get_associated_token_address(&bob_key, &mint_key)).await,
+        100
+    );
+
+    // Replaying the same nonce must be rejected, even with a valid
+    // signature over the same payload.
+    let replay_secp_ix = new_secp256k1_instruction(&authorizer_key, &authorization_message(1));
+    let replay_transfer_ix = create_transfer_with_authorization_instruction(
+        &alice_key,
+        &bob_key,
+        &mint_key,
+        100,
+        expiry_slot,
+        1,
+    )
+    .unwrap();
+    assert!(
+        process_transaction(lwc, vec![replay_secp_ix, replay_transfer_ix], vec![&alice])
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_community_managed_token_transfer_with_authorization_rejects_spliced_instruction_index(
+) {
+    let mut context = community_managed_token_test().start_with_context().await;
+    let lwc = &mut context.banks_client;
+    let authority = Keypair::new();
+    transfer(lwc, &context.payer, &authority.pubkey(), sol(10.0))
+        .await
+        .unwrap();
+    let mint = Keypair::new();
+    let mint_key = mint.pubkey();
+    let create_ix =
+        create_initialize_mint_instruction(&mint_key, &authority.pubkey(), &authority.pubkey(), 0)
+            .unwrap();
+    process_transaction(lwc, vec![create_ix], vec![&authority, &mint])
+        .await
+        .unwrap();
+
+    let alice = Keypair::new();
+    let alice_key = alice.pubkey();
+    let bob = Keypair::new();
+    let bob_key = bob.pubkey();
+
+    for k in [&alice_key, &bob_key] {
+        transfer(lwc, &context.payer, k, sol(1.0)).await.unwrap();
+        let create_ata = create_initialize_account_instruction(
+            &mint_key,
+            k,
+            &authority.pubkey(),
+            &authority.pubkey(),
+        )
+        .unwrap();
+        process_transaction(lwc, vec![create_ata], vec![&authority])
+            .await
+            .unwrap();
+    }
+    let mint_to_ix =
+        create_mint_to_instruction(&mint_key, &alice_key, &authority.pubkey(), 1000).unwrap();
+    process_transaction(lwc, vec![mint_to_ix], vec![&authority])
+        .await
+        .unwrap();
+
+    let authorizer_key = libsecp256k1::SecretKey::parse(&[11u8; 32]).unwrap();
+    let authorizer_address =
+        construct_eth_pubkey(&libsecp256k1::PublicKey::from_secret_key(&authorizer_key));
+    let set_authorizer_ix =
+        create_set_authorizer_instruction(&mint_key, &authority.pubkey(), authorizer_address)
+            .unwrap();
+    process_transaction(lwc, vec![set_authorizer_ix], vec![&authority])
+        .await
+        .unwrap();
+
+    let expiry_slot = lwc.get_sysvar::<Clock>().await.unwrap().slot + 1_000;
+    let forged_amount = 1000u64;
+    let forged_nonce = 99u64;
+    let mut forged_message = Vec::with_capacity(32 * 3 + 8 + 8 + 8);
+    forged_message.extend_from_slice(alice_key.as_ref());
+    forged_message.extend_from_slice(bob_key.as_ref());
+    forged_message.extend_from_slice(mint_key.as_ref());
+    forged_message.extend_from_slice(&forged_amount.to_le_bytes());
+    forged_message.extend_from_slice(&expiry_slot.to_le_bytes());
+    forged_message.extend_from_slice(&forged_nonce.to_le_bytes());
+
+    // A genuine secp256k1 instruction, signed by the real authorizer over
+    // some unrelated payload, sits at instruction index 0. The signature
+    // is real, but it was never signed over `forged_message`. The payload
+    // has the same length as `forged_message`, so the spliced instruction's
+    // offsets and size below fall inside instruction 0's data.
+    let genuine_secp_ix =
+        new_secp256k1_instruction(&authorizer_key, &vec![0xa5u8; forged_message.len()]);
+
+    // A spliced secp256k1 instruction at index 1: every offset-struct
+    // index field claims its signature/eth-address/message bytes live in
+    // instruction index 0 -- which is exactly what the precompile itself
+    // checks, and is true -- but the bytes sitting at those same numeric
+    // offsets within *this* instruction's own data are instead stuffed
+    // with the real authorizer's address next to a message that address
+    // never actually signed.
+    const DATA_START: usize = 12;
+    let message_data_offset = DATA_START + 20 + 64 + 1;
+    let mut spliced_data = vec![0u8; message_data_offset + forged_message.len()];
+    spliced_data[0] = 1;
+    spliced_data[1..3].copy_from_slice(&32u16.to_le_bytes());
+    spliced_data[3] = 0; // signature_instruction_index -> instruction 0
+    spliced_data[4..6].copy_from_slice(&(DATA_START as u16).to_le_bytes());
+    spliced_data[6] = 0; // eth_address_instruction_index -> instruction 0
+    spliced_data[7..9].copy_from_slice(&(message_data_offset as u16).to_le_bytes());
+    spliced_data[9..11].copy_from_slice(&(forged_message.len() as u16).to_le_bytes());
+    spliced_data[11] = 0; // message_instruction_index -> instruction 0
+    spliced_data[DATA_START..DATA_START + 20].copy_from_slice(&authorizer_address);
+    spliced_data[message_data_offset..].copy_from_slice(&forged_message);
+    let spliced_secp_ix = Instruction {
+        program_id: secp256k1_program::id(),
+        accounts: vec![],
+        data: spliced_data,
+    };
+
+    let forged_transfer_ix = create_transfer_with_authorization_instruction(
+        &alice_key,
+        &bob_key,
+        &mint_key,
+        forged_amount,
+        expiry_slot,
+        forged_nonce,
+    )
+    .unwrap();
+
+    assert!(process_transaction(
+        lwc,
+        vec![genuine_secp_ix, spliced_secp_ix, forged_transfer_ix],
+        vec![&alice],
+    )
+    .await
+    .is_err());
+}
+
 #[tokio::test]
 async fn test_community_managed_token_wrap() {
     let mut context = community_managed_token_test().start_with_context().await;
@@ -258,6 +523,9 @@ async fn test_community_managed_token_wrap() {
     transfer(lwc, &context.payer, &old_authority.pubkey(), sol(10.0))
         .await
         .unwrap();
+    transfer(lwc, &context.payer, &new_authority.pubkey(), sol(10.0))
+        .await
+        .unwrap();
 
     let mint = Keypair::new();
     let mint_key = mint.pubkey();
@@ -283,4 +551,428 @@ async fn test_community_managed_token_wrap() {
     process_transaction(lwc, vec![wrap_ix], vec![&old_authority])
         .await
         .unwrap();
+
+    // Alice's account is created and minted to while the mint is
+    // community-managed, so it sits frozen at rest, as every
+    // community-managed account does between instructions.
+    let alice = Keypair::new();
+    let alice_key = alice.pubkey();
+    let bob = Keypair::new();
+    let bob_key = bob.pubkey();
+    for k in [&alice_key, &bob_key] {
+        transfer(lwc, &context.payer, k, sol(1.0)).await.unwrap();
+    }
+    let create_alice_ata_ix = create_initialize_account_instruction(
+        &mint_key,
+        &alice_key,
+        &new_authority.pubkey(),
+        &new_authority.pubkey(),
+    )
+    .unwrap();
+    let mint_to_ix =
+        create_mint_to_instruction(&mint_key, &alice_key, &new_authority.pubkey(), 100).unwrap();
+    process_transaction(
+        lwc,
+        vec![create_alice_ata_ix, mint_to_ix],
+        vec![&old_authority, &new_authority],
+    )
+    .await
+    .unwrap();
+
+    let final_mint_authority = Keypair::new();
+    let final_freeze_authority = Keypair::new();
+    let unwrap_ix = create_unwrap_instruction(
+        &mint_key,
+        &new_authority.pubkey(),
+        &final_mint_authority.pubkey(),
+        &final_freeze_authority.pubkey(),
+    )
+    .unwrap();
+    process_transaction(lwc, vec![unwrap_ix], vec![&old_authority, &new_authority])
+        .await
+        .unwrap();
+
+    // The mint is a plain spl_token mint again, but `Unwrap` does not
+    // thaw accounts that were frozen under community management: Alice's
+    // account is still frozen, so a direct spl_token transfer out of it
+    // still fails.
+    let create_bob_ata = create_associated_token_account(&context.payer.pubkey(), &bob_key, &mint_key);
+    process_transaction(lwc, vec![create_bob_ata], vec![&context.payer])
+        .await
+        .unwrap();
+
+    let still_frozen_transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &get_associated_token_address(&alice_key, &mint_key),
+        &get_associated_token_address(&bob_key, &mint_key),
+        &alice_key,
+        &[],
+        100,
+    )
+    .unwrap();
+    assert!(
+        process_transaction(lwc, vec![still_frozen_transfer_ix], vec![&alice])
+            .await
+            .is_err()
+    );
+
+    // The new freeze authority must thaw it directly, after which a
+    // plain spl_token transfer finally succeeds.
+    let thaw_ix = spl_token::instruction::thaw_account(
+        &spl_token::id(),
+        &get_associated_token_address(&alice_key, &mint_key),
+        &mint_key,
+        &final_freeze_authority.pubkey(),
+        &[],
+    )
+    .unwrap();
+    process_transaction(lwc, vec![thaw_ix], vec![&final_freeze_authority])
+        .await
+        .unwrap();
+
+    let plain_transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &get_associated_token_address(&alice_key, &mint_key),
+        &get_associated_token_address(&bob_key, &mint_key),
+        &alice_key,
+        &[],
+        100,
+    )
+    .unwrap();
+    process_transaction(lwc, vec![plain_transfer_ix], vec![&alice])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_community_managed_token_allowlist() {
+    let mut context = community_managed_token_test().start_with_context().await;
+    let lwc = &mut context.banks_client;
+    let authority = Keypair::new();
+    transfer(lwc, &context.payer, &authority.pubkey(), sol(10.0))
+        .await
+        .unwrap();
+    let mint = Keypair::new();
+    let mint_key = mint.pubkey();
+    let create_ix =
+        create_initialize_mint_instruction(&mint_key, &authority.pubkey(), &authority.pubkey(), 0)
+            .unwrap();
+    process_transaction(lwc, vec![create_ix], vec![&authority, &mint])
+        .await
+        .unwrap();
+
+    let alice = Keypair::new();
+    let alice_key = alice.pubkey();
+    let bob = Keypair::new();
+    let bob_key = bob.pubkey();
+    let eve = Keypair::new();
+    let eve_key = eve.pubkey();
+
+    for k in [&alice_key, &bob_key, &eve_key] {
+        transfer(lwc, &context.payer, k, sol(1.0)).await.unwrap();
+        let create_ata = create_initialize_account_instruction(
+            &mint_key,
+            k,
+            &authority.pubkey(),
+            &authority.pubkey(),
+        )
+        .unwrap();
+        let mint_to_ix =
+            create_mint_to_instruction(&mint_key, k, &authority.pubkey(), 1000).unwrap();
+        process_transaction(lwc, vec![create_ata, mint_to_ix], vec![&authority])
+            .await
+            .unwrap();
+    }
+
+    for k in [&alice_key, &bob_key] {
+        let add_ix = create_add_to_allowlist_instruction(
+            &mint_key,
+            &authority.pubkey(),
+            k,
+            &authority.pubkey(),
+        )
+        .unwrap();
+        process_transaction(lwc, vec![add_ix], vec![&authority])
+            .await
+            .unwrap();
+    }
+
+    // Eve is not allowlisted, so she cannot receive an allowlisted
+    // transfer from Alice.
+    let eve_ix =
+        create_allowlisted_transfer_instruction(&alice_key, &eve_key, &mint_key, 100).unwrap();
+    assert!(
+        process_transaction(lwc, vec![eve_ix], vec![&alice])
+            .await
+            .is_err()
+    );
+
+    // The raw spl_token transfer must still fail for a community-managed
+    // mint, allowlist or not.
+    let failed_transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &get_associated_token_address(&alice_key, &mint_key),
+        &get_associated_token_address(&bob_key, &mint_key),
+        &alice_key,
+        &[],
+        100,
+    )
+    .unwrap();
+    assert!(
+        process_transaction(lwc, vec![failed_transfer_ix], vec![&alice])
+            .await
+            .is_err()
+    );
+
+    // Alice and Bob are both allowlisted, so the transfer succeeds
+    // without the authority as a signer.
+    let successful_transfer_ix =
+        create_allowlisted_transfer_instruction(&alice_key, &bob_key, &mint_key, 100).unwrap();
+    process_transaction(lwc, vec![successful_transfer_ix], vec![&alice])
+        .await
+        .unwrap();
+
+    // A second, unrelated mint's authority cannot remove Bob's allowlist
+    // entry for the first mint: the entry account it passes in must
+    // actually derive from the mint it claims to be acting on.
+    let other_authority = Keypair::new();
+    transfer(lwc, &context.payer, &other_authority.pubkey(), sol(10.0))
+        .await
+        .unwrap();
+    let other_mint = Keypair::new();
+    let other_mint_key = other_mint.pubkey();
+    let create_other_ix = create_initialize_mint_instruction(
+        &other_mint_key,
+        &other_authority.pubkey(),
+        &other_authority.pubkey(),
+        0,
+    )
+    .unwrap();
+    process_transaction(lwc, vec![create_other_ix], vec![&other_authority, &other_mint])
+        .await
+        .unwrap();
+
+    let (bob_entry, _) =
+        community_managed_token::state::AllowlistEntry::find_address(&mint_key, &bob_key, &community_managed_token::id());
+    let (other_config, _) =
+        community_managed_token::state::MintConfig::find_address(&other_mint_key, &community_managed_token::id());
+    let cross_mint_remove_ix = Instruction {
+        program_id: community_managed_token::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(other_mint_key, false),
+            AccountMeta::new_readonly(other_config, false),
+            AccountMeta::new_readonly(other_authority.pubkey(), true),
+            AccountMeta::new(bob_entry, false),
+            AccountMeta::new_readonly(bob_key, false),
+            AccountMeta::new(other_authority.pubkey(), false),
+        ],
+        data: CommunityManagedTokenInstruction::RemoveFromAllowlist.pack(),
+    };
+    assert!(
+        process_transaction(lwc, vec![cross_mint_remove_ix], vec![&other_authority])
+            .await
+            .is_err()
+    );
+
+    // The mint's own authority can remove Bob, after which he can no
+    // longer take part in allowlisted transfers for this mint.
+    let remove_ix = create_remove_from_allowlist_instruction(
+        &mint_key,
+        &authority.pubkey(),
+        &bob_key,
+        &authority.pubkey(),
+    )
+    .unwrap();
+    process_transaction(lwc, vec![remove_ix], vec![&authority])
+        .await
+        .unwrap();
+
+    let revoked_transfer_ix =
+        create_allowlisted_transfer_instruction(&alice_key, &bob_key, &mint_key, 100).unwrap();
+    assert!(
+        process_transaction(lwc, vec![revoked_transfer_ix], vec![&alice])
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_community_managed_token_nft() {
+    let mut context = community_managed_token_test().start_with_context().await;
+    let lwc = &mut context.banks_client;
+    let authority = Keypair::new();
+    transfer(lwc, &context.payer, &authority.pubkey(), sol(10.0))
+        .await
+        .unwrap();
+
+    let mint = Keypair::new();
+    let mint_key = mint.pubkey();
+    let alice = Keypair::new();
+    let alice_key = alice.pubkey();
+
+    let create_ix = create_initialize_nft_instruction(
+        &mint_key,
+        &authority.pubkey(),
+        &authority.pubkey(),
+        &alice_key,
+    )
+    .unwrap();
+    process_transaction(lwc, vec![create_ix], vec![&authority, &mint])
+        .await
+        .unwrap();
+
+    let set_metadata_ix = create_set_metadata_instruction(
+        &mint_key,
+        &authority.pubkey(),
+        &authority.pubkey(),
+        "Overflow Badge".to_string(),
+        "OVFL".to_string(),
+        "https://example.com/badge.json".to_string(),
+    )
+    .unwrap();
+    process_transaction(lwc, vec![set_metadata_ix], vec![&authority])
+        .await
+        .unwrap();
+
+    let (metadata_address, _) =
+        community_managed_token::state::Metadata::find_address(&mint_key, &community_managed_token::id());
+    let metadata_account = lwc
+        .get_account(metadata_address)
+        .await
+        .unwrap()
+        .expect("metadata account should exist");
+    let metadata = Metadata::deserialize(&mut &metadata_account.data[..]).unwrap();
+    assert_eq!(metadata.name, "Overflow Badge");
+    assert_eq!(metadata.symbol, "OVFL");
+    assert_eq!(metadata.uri, "https://example.com/badge.json");
+
+    // Minting is permanently disabled after the one-time mint.
+    let second_mint_ix =
+        create_mint_to_instruction(&mint_key, &alice_key, &authority.pubkey(), 1).unwrap();
+    assert!(
+        process_transaction(lwc, vec![second_mint_ix], vec![&authority])
+            .await
+            .is_err()
+    );
+}
+
+async fn token_balance(client: &mut BanksClient, account: &Pubkey) -> u64 {
+    let data = client.get_account(*account).await.unwrap().unwrap().data;
+    spl_token::state::Account::unpack(&data).unwrap().amount
+}
+
+#[tokio::test]
+async fn test_community_managed_token_batch_transfer() {
+    let mut context = community_managed_token_test().start_with_context().await;
+    let lwc = &mut context.banks_client;
+    let authority = Keypair::new();
+    transfer(lwc, &context.payer, &authority.pubkey(), sol(10.0))
+        .await
+        .unwrap();
+    let mint = Keypair::new();
+    let mint_key = mint.pubkey();
+    let create_ix =
+        create_initialize_mint_instruction(&mint_key, &authority.pubkey(), &authority.pubkey(), 0)
+            .unwrap();
+    process_transaction(lwc, vec![create_ix], vec![&authority, &mint])
+        .await
+        .unwrap();
+
+    let alice = Keypair::new();
+    let alice_key = alice.pubkey();
+    let bob = Keypair::new();
+    let bob_key = bob.pubkey();
+    let carol = Keypair::new();
+    let carol_key = carol.pubkey();
+
+    for k in [&alice_key, &bob_key, &carol_key] {
+        transfer(lwc, &context.payer, k, sol(1.0)).await.unwrap();
+        let create_ata = create_initialize_account_instruction(
+            &mint_key,
+            k,
+            &authority.pubkey(),
+            &authority.pubkey(),
+        )
+        .unwrap();
+        process_transaction(lwc, vec![create_ata], vec![&authority])
+            .await
+            .unwrap();
+    }
+    let mint_to_alice_ix =
+        create_mint_to_instruction(&mint_key, &alice_key, &authority.pubkey(), 1000).unwrap();
+    process_transaction(lwc, vec![mint_to_alice_ix], vec![&authority])
+        .await
+        .unwrap();
+
+    // One leg asks for more than Alice holds, so the whole batch must
+    // revert, leaving every balance untouched.
+    let failing_batch_ix = create_batch_transfer_instruction(
+        &authority.pubkey(),
+        &mint_key,
+        &[(alice_key, bob_key, 100), (alice_key, carol_key, 100_000)],
+    )
+    .unwrap();
+    assert!(
+        process_transaction(lwc, vec![failing_batch_ix], vec![&alice, &authority])
+            .await
+            .is_err()
+    );
+    assert_eq!(
+        token_balance(lwc, &get_associated_token_address(&alice_key, &mint_key)).await,
+        1000
+    );
+    assert_eq!(
+        token_balance(lwc, &get_associated_token_address(&bob_key, &mint_key)).await,
+        0
+    );
+
+    let batch_ix = create_batch_transfer_instruction(
+        &authority.pubkey(),
+        &mint_key,
+        &[(alice_key, bob_key, 100), (alice_key, carol_key, 200)],
+    )
+    .unwrap();
+    process_transaction(lwc, vec![batch_ix], vec![&alice, &authority])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        token_balance(lwc, &get_associated_token_address(&alice_key, &mint_key)).await,
+        700
+    );
+    assert_eq!(
+        token_balance(lwc, &get_associated_token_address(&bob_key, &mint_key)).await,
+        100
+    );
+    assert_eq!(
+        token_balance(lwc, &get_associated_token_address(&carol_key, &mint_key)).await,
+        200
+    );
+
+    // A malformed instruction claiming billions of transfers, with no
+    // payload bytes to back that count, must be rejected as malformed
+    // instruction data rather than attempting a huge allocation.
+    let mut malformed_data = vec![15u8];
+    malformed_data.extend_from_slice(&u32::MAX.to_le_bytes());
+    let malformed_batch_ix = Instruction {
+        program_id: community_managed_token::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(mint_key, false),
+            AccountMeta::new_readonly(
+                community_managed_token::state::MintConfig::find_address(
+                    &mint_key,
+                    &community_managed_token::id(),
+                )
+                .0,
+                false,
+            ),
+            AccountMeta::new_readonly(authority.pubkey(), true),
+        ],
+        data: malformed_data,
+    };
+    assert!(
+        process_transaction(lwc, vec![malformed_batch_ix], vec![&authority])
+            .await
+            .is_err()
+    );
 }